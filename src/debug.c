@@ -0,0 +1,192 @@
+#include "debug.h"
+
+#include <inttypes.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "vm.h"
+
+static size_t simple_inst(FILE *out, const char *name, size_t offset) {
+  fprintf(out, "%s\n", name);
+  return offset + 1;
+}
+
+static size_t byte_inst(FILE *out,
+                        const char *name,
+                        const uint8_t *inst,
+                        size_t len,
+                        size_t offset) {
+  if (offset + 1 >= len) {
+    fprintf(out, "%-16s <truncated>\n", name);
+    return len;
+  }
+
+  fprintf(out, "%-16s %" PRIu8 "\n", name, inst[offset + 1]);
+  return offset + 2;
+}
+
+static uint16_t peek_uint16(const uint8_t *inst, size_t offset) {
+  return (uint16_t)(inst[offset + 1]) << 8 | (uint16_t)(inst[offset]);
+}
+
+// writes a value operand in the bytecode's value encoding (floats are 4-byte
+// IEEE singles) and returns the offset right after it, or len + 1 if the value
+// runs past the end; an invalid value type only consumes its type byte so the
+// instructions after it are still shown
+static size_t print_value(FILE *out,
+                          const uint8_t *inst,
+                          size_t len,
+                          size_t offset) {
+  if (offset >= len) {
+    fprintf(out, "<truncated>");
+    return len + 1;
+  }
+
+  uint8_t type = inst[offset++];
+  switch (type) {
+    case 0: {
+      if (offset + 4 > len) break;
+      int64_t result = 0;
+      for (size_t i = 0; i < 4; i++)
+        result |= (int64_t)(inst[offset + i]) << (i * 8);
+      fprintf(out, "%" PRId64, result);
+      return offset + 4;
+    }
+
+    case 1: {
+      if (offset + 4 > len) break;
+      float result = 0.0f;
+      memcpy(&result, inst + offset, sizeof(float));
+      fprintf(out, "%g", (double)result);
+      return offset + 4;
+    }
+
+    case 2:
+      fprintf(out, "true");
+      return offset;
+
+    case 3:
+      fprintf(out, "false");
+      return offset;
+
+    case 4:
+      fprintf(out, "empty");
+      return offset;
+
+    case 5:
+    case 6: {
+      if (offset + 2 > len) break;
+      uint16_t str_len = peek_uint16(inst, offset);
+      offset += 2;
+      if (offset + str_len > len) break;
+      if (type == 5)
+        fprintf(out, "\"%.*s\"", (int)str_len, (const char *)(inst + offset));
+      else
+        fprintf(out, ":%.*s", (int)str_len, (const char *)(inst + offset));
+      return offset + str_len;
+    }
+
+    default:
+      fprintf(out, "<invalid value type %" PRIu8 ">", type);
+      return offset;
+  }
+
+  fprintf(out, "<truncated>");
+  return len + 1;
+}
+
+static size_t load_inst(FILE *out,
+                        const char *name,
+                        const uint8_t *inst,
+                        size_t len,
+                        size_t offset) {
+  fprintf(out, "%-16s ", name);
+  offset = print_value(out, inst, len, offset + 1);
+  fprintf(out, "\n");
+  return offset > len ? len : offset;
+}
+
+static size_t push_inst(FILE *out,
+                        const char *name,
+                        const uint8_t *inst,
+                        size_t len,
+                        size_t offset) {
+  if (offset + 1 >= len) {
+    fprintf(out, "%-16s <truncated>\n", name);
+    return len;
+  }
+
+  uint8_t count = inst[offset + 1];
+  fprintf(out, "%-16s %" PRIu8 " [", name, count);
+
+  offset += 2;
+  size_t i = 0;
+  for (; i < count && offset < len; i++) {
+    if (i != 0) fprintf(out, ", ");
+    offset = print_value(out, inst, len, offset);
+  }
+
+  if (offset > len) {
+    offset = len;
+  } else if (i < count) {
+    if (i != 0) fprintf(out, ", ");
+    fprintf(out, "<truncated>");
+  }
+
+  fprintf(out, "]\n");
+  return offset;
+}
+
+void disassemble(FILE *out, const char *name, const uint8_t *inst, size_t len) {
+  fprintf(out, "== %s ==\n", name);
+
+  for (size_t offset = 0; offset < len;)
+    offset = disassemble_inst(out, inst, len, offset);
+}
+
+size_t disassemble_inst(FILE *out,
+                        const uint8_t *inst,
+                        size_t len,
+                        size_t offset) {
+  fprintf(out, "%04zu ", offset);
+
+  if (offset >= len) {
+    fprintf(out, "<truncated>\n");
+    return len;
+  }
+
+  InstructionType inst_type = (InstructionType)inst[offset];
+  switch (inst_type) {
+    case INST_LOAD_NEG1:
+      return simple_inst(out, "LOAD_NEG1", offset);
+    case INST_LOAD0:
+      return simple_inst(out, "LOAD0", offset);
+    case INST_LOAD1:
+      return simple_inst(out, "LOAD1", offset);
+    case INST_LOAD2:
+      return simple_inst(out, "LOAD2", offset);
+    case INST_LOAD3:
+      return simple_inst(out, "LOAD3", offset);
+    case INST_LOAD4:
+      return simple_inst(out, "LOAD4", offset);
+    case INST_LOAD5:
+      return simple_inst(out, "LOAD5", offset);
+    case INST_LOAD:
+      return load_inst(out, "LOAD", inst, len, offset);
+    case INST_PUSH:
+      return push_inst(out, "PUSH", inst, len, offset);
+    case INST_POP:
+      return simple_inst(out, "POP", offset);
+    case INST_POPN:
+      return byte_inst(out, "POPN", inst, len, offset);
+    case INST_NIP:
+      return simple_inst(out, "NIP", offset);
+    case INST_NIPN:
+      return byte_inst(out, "NIPN", inst, len, offset);
+    case INST_DUP:
+      return simple_inst(out, "DUP", offset);
+  }
+
+  fprintf(out, "Unknown instruction %" PRIu8 "\n", inst[offset]);
+  return offset + 1;
+}
@@ -0,0 +1,20 @@
+#ifndef FDEBUG_H
+#define FDEBUG_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+// writes every instruction in inst to out with its offset, name, and
+// operands; inst must be the instruction stream after the magic number,
+// version, and error info section, not the whole loaded file
+void disassemble(FILE *out, const char *name, const uint8_t *inst, size_t len);
+
+// writes the instruction at offset to out and returns the offset of the next
+// one
+size_t disassemble_inst(FILE *out,
+                        const uint8_t *inst,
+                        size_t len,
+                        size_t offset);
+
+#endif  // !FDEBUG_H
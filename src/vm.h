@@ -9,8 +9,8 @@
 
 #define CALL_FRAMES_MAX 124
 
-const uint8_t VERSION[3] = {0, 0, 0};
-const uint8_t MAGIC_NUMBER[4] = {0x46, 0x4C, 0x41, 0x4E};
+static const uint8_t VERSION[3] = {0, 0, 0};
+static const uint8_t MAGIC_NUMBER[4] = {0x46, 0x4C, 0x41, 0x4E};
 
 typedef struct ErrorInfo {
   uint16_t line;
@@ -0,0 +1,166 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "debug.h"
+#include "vm.h"
+
+static char *disassemble_to_string(const uint8_t *inst, size_t len) {
+  FILE *out = tmpfile();
+  if (out == NULL) return NULL;
+
+  disassemble(out, "test", inst, len);
+
+  long size = ftell(out);
+  rewind(out);
+
+  char *text = malloc(size + 1);
+  if (text == NULL || fread(text, 1, size, out) != (size_t)size) {
+    free(text);
+    fclose(out);
+    return NULL;
+  }
+
+  text[size] = '\0';
+  fclose(out);
+  return text;
+}
+
+static int check(const char *test_name,
+                 const uint8_t *inst,
+                 size_t len,
+                 const char *expected) {
+  char *actual = disassemble_to_string(inst, len);
+  if (actual == NULL) {
+    printf("%s: failed to capture output\n", test_name);
+    return 1;
+  }
+
+  int failed = strcmp(actual, expected) != 0;
+  if (failed)
+    printf("%s:\n-- expected --\n%s-- actual --\n%s",
+           test_name,
+           expected,
+           actual);
+
+  free(actual);
+  return failed;
+}
+
+static int test_all_instructions(void) {
+  const uint8_t inst[] = {
+      INST_LOAD_NEG1, INST_LOAD0, INST_LOAD5,
+      INST_LOAD, 0, 42, 0, 0, 0,
+      INST_LOAD, 1, 0x00, 0x00, 0xC0, 0x3F,
+      INST_PUSH, 4, 2, 4, 5, 2, 0, 'h', 'i', 6, 2, 0, 'o', 'k',
+      INST_POP, INST_POPN, 3, INST_NIP, INST_NIPN, 2, INST_DUP,
+  };
+
+  return check("test_all_instructions",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 LOAD_NEG1\n"
+               "0001 LOAD0\n"
+               "0002 LOAD5\n"
+               "0003 LOAD             42\n"
+               "0009 LOAD             1.5\n"
+               "0015 PUSH             4 [true, empty, \"hi\", :ok]\n"
+               "0029 POP\n"
+               "0030 POPN             3\n"
+               "0032 NIP\n"
+               "0033 NIPN             2\n"
+               "0035 DUP\n");
+}
+
+static int test_truncated_and_unknown(void) {
+  const uint8_t inst[] = {99, INST_LOAD, 5, 3, 0, 'a'};
+
+  return check("test_truncated_and_unknown",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 Unknown instruction 99\n"
+               "0001 LOAD             <truncated>\n");
+}
+
+static int test_push_short_value_list(void) {
+  const uint8_t inst[] = {INST_PUSH, 5, 2};
+
+  return check("test_push_short_value_list",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 PUSH             5 [true, <truncated>]\n");
+}
+
+static int test_push_truncated_value(void) {
+  const uint8_t inst[] = {INST_PUSH, 2, 0, 1, 0};
+
+  return check("test_push_truncated_value",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 PUSH             2 [<truncated>]\n");
+}
+
+static int test_invalid_value_type(void) {
+  const uint8_t inst[] = {INST_LOAD, 9, INST_POP};
+
+  return check("test_invalid_value_type",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 LOAD             <invalid value type 9>\n"
+               "0002 POP\n");
+}
+
+static int test_byte_operand_truncated(void) {
+  const uint8_t inst[] = {INST_POPN};
+
+  return check("test_byte_operand_truncated",
+               inst,
+               sizeof(inst),
+               "== test ==\n"
+               "0000 POPN             <truncated>\n");
+}
+
+static int test_inst_offset_past_end(void) {
+  const uint8_t inst[] = {INST_POP};
+
+  FILE *out = tmpfile();
+  if (out == NULL) {
+    printf("test_inst_offset_past_end: failed to capture output\n");
+    return 1;
+  }
+
+  size_t next = disassemble_inst(out, inst, sizeof(inst), sizeof(inst));
+
+  char text[32] = {0};
+  rewind(out);
+  size_t read = fread(text, 1, sizeof(text) - 1, out);
+  text[read] = '\0';
+  fclose(out);
+
+  const char *expected = "0001 <truncated>\n";
+  if (next != sizeof(inst) || strcmp(text, expected) != 0) {
+    printf("test_inst_offset_past_end:\n-- expected --\n%s-- actual --\n%s",
+           expected,
+           text);
+    return 1;
+  }
+
+  return 0;
+}
+
+int main(void) {
+  int failures = 0;
+  failures += test_all_instructions();
+  failures += test_truncated_and_unknown();
+  failures += test_push_short_value_list();
+  failures += test_push_truncated_value();
+  failures += test_invalid_value_type();
+  failures += test_byte_operand_truncated();
+  failures += test_inst_offset_past_end();
+  return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}